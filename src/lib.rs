@@ -1,6 +1,9 @@
 extern crate itertools;
 use itertools::Itertools;
 
+pub mod sync;
+pub use sync::{SearchWorker, SyncFuzzyDict, SyncWordDict, SyncWordListNode};
+
 
 /// QuickSilver-esque word matching algorithm
 /// 
@@ -21,11 +24,13 @@ use itertools::Itertools;
 /// 
 
 use std::rc::Rc;
+use std::sync::Arc;
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub type Letter = char;
-pub type FuzzyDict<T> = WordListNode<T>;
-pub type WordDict = FuzzyDict<String>;
+pub type FuzzyDict<T> = WordListNode<Rc<T>>;
 
 /// Priority for fuzzy algorithm
 pub enum FuzzPriority {
@@ -42,16 +47,64 @@ pub enum SearchKind {
     /// Search for a prefix match with specified depth
     Prefix(usize),
     /// Search for a fuzzy prefix match with specified fuzz
-    Fuzzy(usize, FuzzPriority)
+    Fuzzy(usize, FuzzPriority),
+    /// Search for all terms within the specified Levenshtein edit distance, via an exact trie walk
+    /// instead of `Fuzzy`'s greedy skip heuristic
+    EditDistance(usize)
+}
+
+/// A single parsed atom of a `find_terms_query` mini-language query
+enum QueryAtom<'a> {
+    /// `^foo`: prefix match from the trie root
+    Prefix(&'a str),
+    /// `foo$`: suffix match on the term representation
+    Suffix(&'a str),
+    /// `'foo`: exact substring match on the term representation
+    Substring(&'a str),
+    /// `!foo`: exclude terms that would match bare `foo`
+    Negated(&'a str),
+    /// A bare atom: an in-order abbreviation (subsequence) match on the term representation
+    Abbreviation(&'a str)
+}
+
+/// Abstracts over the reference-counting pointer a trie node stores its terms as, so the core
+/// matching algorithm below is written once and shared by the single-threaded, `Rc`-backed
+/// `WordDict` and the `Arc`-backed `SyncWordDict`.
+pub trait TermPtr: Clone + std::cmp::Eq + std::hash::Hash {}
+
+impl<T: std::cmp::Eq + std::hash::Hash> TermPtr for Rc<T> {}
+impl<T: std::cmp::Eq + std::hash::Hash> TermPtr for Arc<T> {}
+
+/// Cooperative-cancellation token threaded through the trie walk and checked between branches:
+/// `Some` when running on a `SyncWordDict`'s background thread, so a query superseded by a newer
+/// one can abandon the walk early; `None` for the plain, uncancellable `WordDict` walk.
+#[derive(Clone, Copy)]
+struct Cancellation<'a> {
+    generation: &'a AtomicUsize,
+    expected: usize
+}
+
+impl<'a> Cancellation<'a> {
+    fn new(generation: &'a AtomicUsize, expected: usize) -> Self {
+        Self { generation, expected }
+    }
+
+    fn is_stale(&self) -> bool {
+        self.generation.load(Ordering::Relaxed) != self.expected
+    }
 }
 
-pub struct WordListNode<Term> where Term: std::cmp::Eq + std::hash::Hash {
+fn is_stale(cancel: Option<Cancellation>) -> bool {
+    cancel.is_some_and(|token| token.is_stale())
+}
+
+pub struct WordListNode<P: TermPtr> {
     // Contains pointers to terms
-    terms: HashSet<Rc<Term>>,
+    terms: HashSet<P>,
     children: HashMap<Letter, Self>
 }
 
-impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
+impl<P: TermPtr> WordListNode<P> {
     /// Creates an empty wordlist node
     pub fn new() -> Self {
         Self {
@@ -77,27 +130,32 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
     }
 
     /// Learns a single term, which may consist of multiple words, separated by whitespace
-    pub fn learn_term(&mut self, term_repr: Rc<String>, term: Rc<T>) {
+    pub fn learn_term(&mut self, term_repr: &str, term: P) {
         for word in term_repr.split(|chr: char| !chr.is_alphanumeric()) {
-            // Create a branch for the current word
-            self.learn_word(word)
-            // And add the term to it's end node
-                .terms.insert(term.clone());
+            self.learn_word(word).terms.insert(term.clone());
         }
 
-        let no_spaces = term_repr.chars().filter(|c| c.is_alphanumeric()).collect::<String>();
+        let no_spaces: String = term_repr.chars().filter(|c| c.is_alphanumeric()).collect();
         self.learn_word(&no_spaces).terms.insert(term);
     }
 
-    /// Collect all the terms from this node and down to the specified node depth (Recursive)
-    fn collect_terms(&self, depth: Option<usize>) -> Vec<Rc<T>> {
-        let mut terms: Vec<Rc<T>> = self.terms.iter().map(|r| r.clone()).collect();
-        
+    /// Collect all the terms from this node and down to the specified node depth (Recursive),
+    /// abandoning the walk (and returning whatever was gathered so far) once `cancel` goes stale
+    fn collect_terms(&self, depth: Option<usize>, cancel: Option<Cancellation>) -> Vec<P> {
+        let mut terms: Vec<P> = self.terms.iter().cloned().collect();
+
+        if is_stale(cancel) {
+            return terms;
+        }
+
         // If depth is provided, only go as far as that depth
         if let Some(depth) = depth {
             if depth > 0 {
                 for (_, child) in self.children.iter() {
-                    terms.append(&mut child.collect_terms(Some(depth - 1)));
+                    if is_stale(cancel) {
+                        break;
+                    }
+                    terms.append(&mut child.collect_terms(Some(depth - 1), cancel));
                 }
             }
         }
@@ -105,21 +163,24 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
         else {
             // try to find from the nodes below
             for (_, child) in self.children.iter() {
-                terms.append(&mut child.collect_terms(None));
+                if is_stale(cancel) {
+                    break;
+                }
+                terms.append(&mut child.collect_terms(None, cancel));
             }
         }
         terms
     }
 
     /// Try to greedy find the next node that could match a character
-    fn hope_for_success(&self, chara: &char, fuzz: usize) -> Option<&Self> {
-        if fuzz > 0 {
+    fn hope_for_success(&self, chara: &char, fuzz: usize, cancel: Option<Cancellation>) -> Option<&Self> {
+        if fuzz > 0 && !is_stale(cancel) {
             for (child_char, child) in self.children.iter() {
                 if child_char == chara {
                     return Some(child)
                 }
 
-                match child.hope_for_success(chara, fuzz - 1) {
+                match child.hope_for_success(chara, fuzz - 1, cancel) {
                     Some(rslt) => return Some(rslt),
                     _ => ()
                 }
@@ -128,16 +189,86 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
         None
     }
 
-    /// Perform a strict query prefix search with specified depth fuzz
-    pub fn find_terms(&self, query: &str, kind: SearchKind) -> Vec<Rc<T>> {
+    /// Recursively walk the trie maintaining the Levenshtein dynamic-programming row for `query`,
+    /// pruning any subtree whose best possible distance already exceeds `k`
+    fn walk_edit_distance(&self, query: &[char], row: &[usize], k: usize, out: &mut HashMap<P, usize>, cancel: Option<Cancellation>) {
+        if is_stale(cancel) {
+            return;
+        }
+
+        let len = query.len();
+        let dist = row[len];
+        if dist <= k && !self.terms.is_empty() {
+            for term in self.terms.iter() {
+                let best = out.entry(term.clone()).or_insert(dist);
+                if dist < *best {
+                    *best = dist;
+                }
+            }
+        }
+
+        for (c, child) in self.children.iter() {
+            if is_stale(cancel) {
+                return;
+            }
+
+            let mut next_row = vec![row[0] + 1];
+            for j in 1..=len {
+                let cost = if query[j - 1] != *c { 1 } else { 0 };
+                next_row.push((row[j] + 1).min(next_row[j - 1] + 1).min(row[j - 1] + cost));
+            }
+            if next_row.iter().copied().min().unwrap_or(usize::MAX) <= k {
+                child.walk_edit_distance(query, &next_row, k, out, cancel);
+            }
+        }
+    }
+
+    /// Find all terms within Levenshtein edit distance `k` of `query`, tagged with their distance.
+    ///
+    /// Unlike `Fuzzy`, this walks the whole trie maintaining a DP row, so the distance bound is
+    /// exact rather than a greedy skip heuristic, at the cost of visiting more nodes.
+    fn find_terms_with_distance_impl(&self, query: &str, k: usize, cancel: Option<Cancellation>) -> Vec<(P, usize)> {
+        let lower_query: Vec<char> = query.to_lowercase().chars().filter(|x| x.is_alphanumeric()).collect();
+        let root_row: Vec<usize> = (0..=lower_query.len()).collect();
+
+        let mut found: HashMap<P, usize> = HashMap::new();
+        self.walk_edit_distance(&lower_query, &root_row, k, &mut found, cancel);
+
+        let mut result: Vec<(P, usize)> = found.into_iter().collect();
+        result.sort_by_key(|(_, dist)| *dist);
+        result
+    }
+
+    /// Perform a strict query prefix search with specified depth fuzz, abandoning the walk once
+    /// `cancel` goes stale
+    fn find_terms_impl(&self, query: &str, kind: SearchKind, cancel: Option<Cancellation>) -> Vec<P> {
         use std::iter::FromIterator;
 
+        if is_stale(cancel) {
+            return vec![];
+        }
+
+        if let SearchKind::EditDistance(k) = &kind {
+            let found = self.find_terms_with_distance_impl(query, *k, cancel);
+            if is_stale(cancel) {
+                return vec![];
+            }
+            return found.into_iter().map(|(term, _)| term).collect();
+        }
+
         let mut now_node = self;
-        let max_i = query.len() - 1;
+        // `saturating_sub` avoids underflowing on an empty query (e.g. a bare `^` atom in
+        // `find_terms_query`) — the loop below never iterates in that case anyway, since
+        // `lower_query` is empty too, so the value is simply unused
+        let max_i = query.len().saturating_sub(1);
         let lower_query: String = query.to_lowercase().chars().filter(|x| x.is_alphanumeric()).collect();
-        let mut restrict_to: HashSet<Rc<T>> = HashSet::new();
+        let mut restrict_to: HashSet<P> = HashSet::new();
 
         for (i, c) in  lower_query.chars().enumerate() {
+            if is_stale(cancel) {
+                return vec![];
+            }
+
             match now_node.children.get(&c) {
                 None => {
                     if i != max_i {
@@ -148,35 +279,35 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
 
                                 // to avoid false positives, restrict to those which would have appeared if we continued to match, but only once
                                 if restrict_to.len() == 0 {
-                                    let could_have_been = now_node.collect_terms(None);
+                                    let could_have_been = now_node.collect_terms(None, cancel);
                                     restrict_to.extend(could_have_been.into_iter());
                                 }
 
                                 match pri {
                                     FuzzPriority::WordBoundary => {
                                         // Try to find new word beginning with current char
-                                        match self.hope_for_success(&c, 1) {
+                                        match self.hope_for_success(&c, 1, cancel) {
                                             Some(alt_word) => {
-                                                let new_candidates = HashSet::from_iter(alt_word.collect_terms(None).into_iter());
+                                                let new_candidates = HashSet::from_iter(alt_word.collect_terms(None, cancel).into_iter());
                                                 if new_candidates.intersection(&restrict_to).count() > 0 {
                                                     now_node = alt_word; // found alternate node in another word among current results, continue from there
                                                     continue;
                                                 }
                                                 // else fall through to typo correction
-                                            }, 
+                                            },
                                             None => () // fall through to typo correction
                                         }
                                     }
 
                                     FuzzPriority::TypoCorrection => {
-                                        match now_node.hope_for_success(&c, fuzz) {
+                                        match now_node.hope_for_success(&c, fuzz, cancel) {
                                             Some(alt_node) => {
                                                 // found an alternate node matching current char, continue from there
                                                 now_node = alt_node;
-                                            }, 
+                                            },
                                             None => {
                                                 // found no alternate node further, last hope is try from the root
-                                                match self.hope_for_success(&c, 1) {
+                                                match self.hope_for_success(&c, 1, cancel) {
                                                     Some(alt_word) => {
                                                         now_node = alt_word;
                                                     }, // found alternate node in another word, continue from there
@@ -194,22 +325,26 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
                 Some(x) => now_node = x
             }
         }
-        
+
         let depth_limit = match kind {
             SearchKind::Fuzzy(_, _) => None,
             SearchKind::Prefix(depth) => Some(depth),
-            SearchKind::Strict => Some(0)
+            SearchKind::Strict => Some(0),
+            SearchKind::EditDistance(_) => unreachable!("handled by the early return above")
         };
-        let res = now_node.collect_terms(depth_limit);
+        let res = now_node.collect_terms(depth_limit, cancel);
+        if is_stale(cancel) {
+            return vec![];
+        }
 
-        let rslt: Vec<Rc<T>> = res.into_iter()
+        let rslt: Vec<P> = res.into_iter()
                         .unique()
                         .filter(|x| if restrict_to.len() > 0 { restrict_to.contains(x) } else { true } )
                         .collect();
         if rslt.len() == 0 {
             match kind {
                 SearchKind::Fuzzy(fuzz, pri) => match pri {
-                    FuzzPriority::TypoCorrection =>  return self.find_terms(query, SearchKind::Fuzzy(fuzz, FuzzPriority::WordBoundary)),
+                    FuzzPriority::TypoCorrection =>  return self.find_terms_impl(query, SearchKind::Fuzzy(fuzz, FuzzPriority::WordBoundary), cancel),
                     _ => ()
                 }
                 _ => ()
@@ -219,10 +354,308 @@ impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug> WordListNode<T> {
     }
 }
 
+impl<T: std::cmp::Eq + std::hash::Hash> WordListNode<Rc<T>> {
+    /// Perform a strict query prefix search with specified depth fuzz
+    pub fn find_terms(&self, query: &str, kind: SearchKind) -> Vec<Rc<T>> {
+        self.find_terms_impl(query, kind, None)
+    }
+
+    /// Find all terms within Levenshtein edit distance `k` of `query`, tagged with their distance.
+    ///
+    /// Unlike `Fuzzy`, this walks the whole trie maintaining a DP row, so the distance bound is
+    /// exact rather than a greedy skip heuristic, at the cost of visiting more nodes.
+    pub fn find_terms_with_distance(&self, query: &str, k: usize) -> Vec<(Rc<T>, usize)> {
+        self.find_terms_with_distance_impl(query, k, None)
+    }
+}
+
+/// Count the character trigrams of `s`, lowercased with whitespace stripped and padded with a
+/// leading/trailing space so word boundaries count towards the similarity too
+fn trigram_counts(s: &str) -> HashMap<(char, char, char), usize> {
+    let cleaned: String = s.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+    let padded = format!(" {} ", cleaned);
+    let padded_chars: Vec<char> = padded.chars().collect();
+
+    let mut counts = HashMap::new();
+    if padded_chars.len() >= 3 {
+        for w in padded_chars.windows(3) {
+            *counts.entry((w[0], w[1], w[2])).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Sørensen–Dice similarity over the trigram multisets of `query` and `term`
+fn trigram_similarity(query: &str, term: &str) -> f32 {
+    let a = trigram_counts(query);
+    let b = trigram_counts(term);
+
+    let shared: usize = a.iter()
+        .filter_map(|(trigram, &count)| b.get(trigram).map(|&other| count.min(other)))
+        .sum();
+    let total = a.values().sum::<usize>() + b.values().sum::<usize>();
+
+    if total == 0 { 0.0 } else { (2 * shared) as f32 / total as f32 }
+}
+
+/// Fraction of `query`'s letters that appear, in order, somewhere in `term` — rewards
+/// abbreviations like "phosh" for "Photoshop" that trigram overlap alone undersells
+fn subsequence_coverage(query: &str, term: &str) -> f32 {
+    let query_chars: Vec<char> = query.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+    if query_chars.is_empty() {
+        return 0.0;
+    }
+    let term_chars: Vec<char> = term.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+
+    // A query letter missing from the rest of the term is skipped without moving the cursor, so
+    // it doesn't stop later letters from still matching further on
+    let mut cursor = 0;
+    let mut matched = 0;
+    for qc in &query_chars {
+        if let Some(pos) = term_chars[cursor..].iter().position(|tc| tc == qc) {
+            cursor += pos + 1;
+            matched += 1;
+        }
+    }
+    matched as f32 / query_chars.len() as f32
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash + std::fmt::Debug + AsRef<str>> WordListNode<Rc<T>> {
+    /// Perform `find_terms`, then rank the candidate set by similarity to `query`, descending.
+    ///
+    /// `find_terms` stays the boolean gate that decides which terms are candidates at all; this
+    /// blends trigram Sørensen–Dice similarity with an in-order subsequence bonus to give callers
+    /// a stable, meaningful ordering as promised by the crate's "ranked by similarity" doc comment.
+    pub fn find_terms_ranked(&self, query: &str, kind: SearchKind) -> Vec<(Rc<T>, f32)> {
+        let candidates = self.find_terms(query, kind);
+
+        let mut ranked: Vec<(Rc<T>, f32)> = candidates.into_iter().map(|term| {
+            let repr: &str = term.as_ref().as_ref();
+            let score = 0.75 * trigram_similarity(query, repr) + 0.25 * subsequence_coverage(query, repr);
+            (term, score)
+        }).collect();
+
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// Parse one whitespace-separated atom of a `find_terms_query` query into its mode
+    fn parse_query_atom(raw: &str) -> QueryAtom<'_> {
+        if let Some(needle) = raw.strip_prefix('^') {
+            QueryAtom::Prefix(needle)
+        } else if let Some(needle) = raw.strip_prefix('\'') {
+            QueryAtom::Substring(needle)
+        } else if let Some(needle) = raw.strip_prefix('!') {
+            QueryAtom::Negated(needle)
+        } else if let Some(needle) = raw.strip_suffix('$') {
+            QueryAtom::Suffix(needle)
+        } else {
+            QueryAtom::Abbreviation(raw)
+        }
+    }
+
+    /// All terms anywhere in the dictionary, for the atom modes that can't be answered by a
+    /// single trie descent
+    fn all_terms(&self) -> HashSet<Rc<T>> {
+        self.collect_terms(None, None).into_iter().collect()
+    }
+
+    /// Terms whose representation contains `needle` as a substring, case-insensitively
+    fn terms_containing(&self, needle: &str) -> HashSet<Rc<T>> {
+        let needle = needle.to_lowercase();
+        self.all_terms().into_iter()
+            .filter(|term| term.as_ref().as_ref().to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    /// Terms whose representation ends with `needle`, case-insensitively
+    fn terms_ending_with(&self, needle: &str) -> HashSet<Rc<T>> {
+        let needle = needle.to_lowercase();
+        self.all_terms().into_iter()
+            .filter(|term| term.as_ref().as_ref().to_lowercase().ends_with(&needle))
+            .collect()
+    }
+
+    /// The default abbreviation search used for bare atoms and negations in `find_terms_query`:
+    /// a term matches if `needle`'s letters appear, in order, somewhere in the term (possibly
+    /// crossing word boundaries), the same notion of "abbreviation" `subsequence_coverage` scores
+    /// for ranking. `find_terms`'s `Fuzzy` mode is unsuitable here — its trie walk is built to
+    /// track a *single* best-effort position through the trie and recover from one mismatch at a
+    /// time, so on a bare atom like "shop" that doesn't share a prefix with any learned word, it
+    /// gives up on the early letters entirely and falls back to matching on the tail alone,
+    /// returning every term instead of narrowing to the ones actually containing "shop".
+    fn terms_matching_abbreviation(&self, needle: &str) -> HashSet<Rc<T>> {
+        let needle_chars: Vec<char> = needle.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+
+        self.all_terms().into_iter()
+            .filter(|term| {
+                let term_chars: Vec<char> = term.as_ref().as_ref().to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+                let mut term_iter = term_chars.iter();
+                needle_chars.iter().all(|nc| term_iter.any(|tc| tc == nc))
+            })
+            .collect()
+    }
+
+    /// Resolve a single query atom (anything but `Negated`, which the caller handles separately)
+    /// to its matching term set
+    fn resolve_query_atom(&self, atom: &QueryAtom) -> HashSet<Rc<T>> {
+        match atom {
+            QueryAtom::Prefix(needle) => self.find_terms(needle, SearchKind::Prefix(usize::MAX)).into_iter().collect(),
+            QueryAtom::Suffix(needle) => self.terms_ending_with(needle),
+            QueryAtom::Substring(needle) => self.terms_containing(needle),
+            QueryAtom::Abbreviation(needle) => self.terms_matching_abbreviation(needle),
+            QueryAtom::Negated(needle) => self.terms_matching_abbreviation(needle)
+        }
+    }
+
+    /// A picker-style query mini-language on top of `find_terms`.
+    ///
+    /// The query is split on whitespace into atoms, each constraining the result set:
+    /// * `^foo` anchors a prefix match from the trie root
+    /// * `foo$` requires the term to end with `foo`
+    /// * `'foo` requires an exact (case-insensitive) substring match
+    /// * `!foo` negates: excludes any term that would match bare `foo`
+    /// * a bare atom matches terms containing its letters in order, as an abbreviation; this is
+    ///   a subsequence filter over all terms, not `find_terms`'s `Fuzzy` trie walk
+    ///
+    /// The result is the intersection of the positive atoms, minus the union of the negated ones,
+    /// so e.g. `^pho !booth shop` resolves to "Adobe Photoshop" but not "Photo Booth".
+    pub fn find_terms_query(&self, query: &str) -> Vec<Rc<T>> {
+        let mut positive: Option<HashSet<Rc<T>>> = None;
+        let mut negative: HashSet<Rc<T>> = HashSet::new();
+
+        for raw_atom in query.split_whitespace() {
+            let atom = Self::parse_query_atom(raw_atom);
+            if let QueryAtom::Negated(needle) = atom {
+                negative.extend(self.terms_matching_abbreviation(needle));
+                continue;
+            }
+
+            let matches = self.resolve_query_atom(&atom);
+            positive = Some(match positive {
+                Some(acc) => acc.intersection(&matches).cloned().collect(),
+                None => matches
+            });
+        }
+
+        positive.unwrap_or_default().difference(&negative).cloned().collect()
+    }
+}
+
+/// The top-level dictionary: a `WordListNode<Rc<String>>` trie plus, for each learned term, the
+/// original-representation char offsets of its alphanumeric-only representation (kept here,
+/// keyed by term, rather than on the trie's `WordListNode`s).
+pub struct WordDict {
+    root: FuzzyDict<String>,
+    origins: HashMap<Rc<String>, Vec<usize>>
+}
+
+impl Default for WordDict {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WordDict {
+    /// Creates an empty dictionary
+    pub fn new() -> Self {
+        Self {
+            root: WordListNode::new(),
+            origins: HashMap::new()
+        }
+    }
+
     pub fn learn(&mut self, term: String) {
         let rc = Rc::new(term);
-        self.learn_term(rc.clone(), rc);
+        self.root.learn_term(&rc, rc.clone());
+        self.record_origins(&rc);
+    }
+
+    /// Record the original-representation char offsets of `term`'s alphanumeric-only
+    /// representation, so `find_terms_highlighted` can reconstruct highlight ranges.
+    fn record_origins(&mut self, term: &Rc<String>) {
+        let offsets = term.chars().enumerate()
+            .filter(|(_, c)| c.is_alphanumeric())
+            .map(|(i, _)| i)
+            .collect();
+        self.origins.insert(term.clone(), offsets);
+    }
+
+    pub fn find_terms(&self, query: &str, kind: SearchKind) -> Vec<Rc<String>> {
+        self.root.find_terms(query, kind)
+    }
+
+    pub fn find_terms_with_distance(&self, query: &str, k: usize) -> Vec<(Rc<String>, usize)> {
+        self.root.find_terms_with_distance(query, k)
+    }
+
+    pub fn find_terms_ranked(&self, query: &str, kind: SearchKind) -> Vec<(Rc<String>, f32)> {
+        self.root.find_terms_ranked(query, kind)
+    }
+
+    pub fn find_terms_query(&self, query: &str) -> Vec<Rc<String>> {
+        self.root.find_terms_query(query)
+    }
+
+    /// Greedily align `query`'s chars against the segment's chars, in order, returning each
+    /// matched (original offset, matched query char) pair. A query char not found anywhere in
+    /// the rest of the segment restores the cursor to where it started, so it doesn't block
+    /// later query chars from still matching further on.
+    fn align_segment(query: &[char], segment_chars: &[char], offsets: &[usize]) -> Vec<(usize, char)> {
+        let mut matched = Vec::new();
+        let mut cursor = 0;
+        for &qc in query {
+            let start = cursor;
+            let mut found = false;
+            while cursor < segment_chars.len() {
+                // `query` is already fully Unicode-lowercased (see `find_terms_highlighted`), so
+                // compare with `.to_lowercase()` here too — `.to_ascii_lowercase()` is a no-op on
+                // non-ASCII letters (Cyrillic, Greek, accented Latin, ...) and would silently fail
+                // to match them.
+                let mut lower = segment_chars[cursor].to_lowercase();
+                let hit = lower.next() == Some(qc) && lower.next().is_none();
+                let offset = offsets[cursor];
+                cursor += 1;
+                if hit {
+                    matched.push((offset, qc));
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                cursor = start;
+            }
+        }
+        matched
+    }
+
+    /// Merge a sequence of matched original offsets into contiguous ranges; a gap starts a new one
+    fn offsets_to_ranges(offsets: &[usize]) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for &offset in offsets {
+            match ranges.last_mut() {
+                Some(r) if r.end == offset => r.end = offset + 1,
+                _ => ranges.push(offset..offset + 1)
+            }
+        }
+        ranges
+    }
+
+    /// Perform `find_terms`, then report which character ranges of each matched term's original
+    /// representation the query actually matched, for UI highlighting. Matched offsets are merged
+    /// into contiguous ranges, so a skipped typo or gap produces a separate range.
+    pub fn find_terms_highlighted(&self, query: &str, kind: SearchKind) -> Vec<(Rc<String>, Vec<Range<usize>>)> {
+        let lower_query: Vec<char> = query.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect();
+
+        self.find_terms(query, kind).into_iter().map(|term| {
+            let offsets = self.origins.get(&term).cloned().unwrap_or_default();
+            let repr_chars: Vec<char> = term.chars().collect();
+            let segment_chars: Vec<char> = offsets.iter().map(|&i| repr_chars[i]).collect();
+            let matched = Self::align_segment(&lower_query, &segment_chars, &offsets);
+            let ranges = Self::offsets_to_ranges(&matched.into_iter().map(|(offset, _)| offset).collect::<Vec<_>>());
+
+            (term, ranges)
+        }).collect()
     }
 }
 
@@ -305,4 +738,97 @@ mod tests {
         check_finds(&dict, "miminishiage", SearchKind::Fuzzy(FUZZ, FuzzPriority::TypoCorrection), "miku miku ni shite ageru");
         check_finds(&dict, "woismi", SearchKind::Fuzzy(FUZZ, FuzzPriority::TypoCorrection), "World Is Mine");
     }
+
+    #[test]
+    fn it_reports_highlighted_spans() {
+        let mut dict = WordDict::new();
+        dict.learn(String::from("Hello World"));
+
+        let rslt = dict.find_terms_highlighted("helwor", SearchKind::Fuzzy(5, FuzzPriority::TypoCorrection));
+        assert_eq!( rslt.len(), 1 );
+
+        let (term, ranges) = &rslt[0];
+        assert_eq!( ranges, &vec![0..3, 6..9] );
+
+        let highlighted: Vec<&str> = ranges.iter().map(|r| &term.as_str()[r.clone()]).collect();
+        assert_eq!( highlighted, vec!["Hel", "Wor"] );
+    }
+
+    #[test]
+    fn it_searches_by_edit_distance() {
+        let mut dict = WordDict::new();
+
+        dict.learn(String::from("hello"));
+        dict.learn(String::from("world"));
+
+        // One substitution away from "hello"
+        assert!( dict.find_terms("hallo", SearchKind::EditDistance(1)).len() == 1 );
+        // Not an exact match for either term, so nothing within distance 0
+        assert!( dict.find_terms("hallo", SearchKind::EditDistance(0)).len() == 0 );
+
+        // Distance is exact: the bound must include all edits, not just a fixed number of skips
+        let tagged = dict.find_terms_with_distance("world", 2);
+        assert!( tagged.iter().any(|(term, dist)| term.as_str() == "world" && *dist == 0) );
+        assert!( tagged.iter().any(|(term, _)| term.as_str() == "hello") == false );
+    }
+
+    #[test]
+    fn it_ranks_by_similarity() {
+        use std::borrow::Borrow;
+
+        let mut dict = WordDict::new();
+
+        dict.learn(String::from("hello"));
+        dict.learn(String::from("help"));
+
+        let ranked = dict.find_terms_ranked("hel", SearchKind::Prefix(10));
+        assert_eq!( ranked.len(), 2 );
+
+        // Scores come back sorted descending
+        for pair in ranked.windows(2) {
+            assert!( pair[0].1 >= pair[1].1 );
+        }
+
+        // "help" is a closer match to "hel" than "hello" (less left over after the shared prefix)
+        let best: &String = ranked[0].0.borrow();
+        assert_eq!( best, "help" );
+    }
+
+    #[test]
+    fn it_parses_query_mini_language() {
+        use std::borrow::Borrow;
+
+        let mut dict = WordDict::new();
+        dict.learn(String::from("Photos"));
+        dict.learn(String::from("Photo Booth"));
+        dict.learn(String::from("Adobe Photoshop"));
+        dict.learn(String::from("Photo Magic"));
+
+        fn names(dict: &WordDict, query: &str) -> Vec<String> {
+            dict.find_terms_query(query).iter().map(|t| {
+                let s: &String = t.borrow();
+                s.clone()
+            }).collect()
+        }
+
+        // Prefix atom alone matches every term with a word starting "photo"
+        assert_eq!( names(&dict, "^photo").len(), 4 );
+
+        // Negation narrows the set
+        let without_booth = names(&dict, "^photo !booth");
+        assert!( without_booth.contains(&String::from("Adobe Photoshop")) );
+        assert!( without_booth.contains(&String::from("Photo Magic")) );
+        assert!( without_booth.contains(&String::from("Photos")) );
+        assert!( !without_booth.contains(&String::from("Photo Booth")) );
+
+        // A bare atom narrows further, to just the term containing it as an abbreviation
+        let narrowed = names(&dict, "^pho !booth shop");
+        assert_eq!( narrowed, vec![String::from("Adobe Photoshop")] );
+
+        // Suffix atom
+        assert!( names(&dict, "gic$").contains(&String::from("Photo Magic")) );
+
+        // A bare prefix sigil with nothing after it is "no constraint", not a crash
+        assert_eq!( names(&dict, "^").len(), 4 );
+    }
 }
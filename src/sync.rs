@@ -0,0 +1,181 @@
+//! `Arc`-backed counterpart to the `Rc`-based trie, plus a background `SearchWorker` that makes
+//! it usable behind an interactive launcher UI without the caller hand-rolling threading.
+//!
+//! `WordDict` uses `Rc`, so it can't be shared with a background thread. `SyncWordDict` mirrors
+//! its learning and its `find_terms`, `find_terms_with_distance` and `find_terms_ranked` search
+//! with `Arc` instead; `find_terms_query` and `find_terms_highlighted` exist on `WordDict` only.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::{SearchKind, WordListNode};
+
+/// `Arc`-backed counterpart to `WordListNode<Rc<T>>`, safe to share across threads. The matching
+/// algorithm itself lives once in `WordListNode`, generic over the smart-pointer type; this is
+/// just the `Arc` specialization plus the `Cancellation`-aware wrappers a `SearchWorker` needs.
+pub type SyncWordListNode<T> = WordListNode<Arc<T>>;
+pub type SyncFuzzyDict<T> = SyncWordListNode<T>;
+pub type SyncWordDict = SyncFuzzyDict<String>;
+
+impl<T: Eq + Hash> WordListNode<Arc<T>> {
+    /// Like `WordListNode::find_terms`, but abandons the walk — returning an empty result — the
+    /// moment `generation` no longer matches `expected`, i.e. a newer query has superseded this one
+    pub fn find_terms(&self, query: &str, kind: SearchKind, generation: &AtomicUsize, expected: usize) -> Vec<Arc<T>> {
+        self.find_terms_impl(query, kind, Some(crate::Cancellation::new(generation, expected)))
+    }
+
+    /// Like `WordListNode::find_terms_with_distance`, but abandons the walk once superseded
+    pub fn find_terms_with_distance(&self, query: &str, k: usize, generation: &AtomicUsize, expected: usize) -> Vec<(Arc<T>, usize)> {
+        self.find_terms_with_distance_impl(query, k, Some(crate::Cancellation::new(generation, expected)))
+    }
+}
+
+impl<T: Eq + Hash + Debug + AsRef<str>> WordListNode<Arc<T>> {
+    /// Like `WordListNode::find_terms_ranked`: find candidates via `find_terms`, then rank them by
+    /// similarity to `query`, descending. Checks `generation` one last time before scoring, so a
+    /// superseded query doesn't pay for ranking a result nobody asked for any more.
+    pub fn find_terms_ranked(&self, query: &str, kind: SearchKind, generation: &AtomicUsize, expected: usize) -> Vec<(Arc<T>, f32)> {
+        let candidates = self.find_terms(query, kind, generation, expected);
+        if generation.load(Ordering::Relaxed) != expected {
+            return vec![];
+        }
+
+        let mut ranked: Vec<(Arc<T>, f32)> = candidates.into_iter().map(|term| {
+            let repr: &str = term.as_ref().as_ref();
+            let score = 0.75 * crate::trigram_similarity(query, repr) + 0.25 * crate::subsequence_coverage(query, repr);
+            (term, score)
+        }).collect();
+
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+}
+
+impl SyncWordDict {
+    pub fn learn(&mut self, term: String) {
+        let arc = Arc::new(term);
+        self.learn_term(&arc, arc.clone());
+    }
+}
+
+type RankedResults = (usize, Vec<(Arc<String>, f32)>);
+
+/// Owns a `SyncWordDict` on a background thread, accepting query updates over a channel and
+/// streaming ranked results back. Each call to `search` bumps a shared generation counter, so the
+/// walk for a superseded query abandons early instead of blocking a newer one.
+pub struct SearchWorker {
+    query_tx: Option<mpsc::Sender<(String, SearchKind, usize)>>,
+    generation: Arc<AtomicUsize>,
+    handle: Option<thread::JoinHandle<()>>
+}
+
+impl SearchWorker {
+    /// Spawn the worker thread. Ranked results for query generation `N` are sent to `results_tx`
+    /// as `(N, ranked)`, so the caller can discard anything that doesn't match the generation of
+    /// the query it cares about.
+    pub fn spawn(dict: Arc<SyncWordDict>, results_tx: mpsc::Sender<RankedResults>) -> Self {
+        let (query_tx, query_rx) = mpsc::channel::<(String, SearchKind, usize)>();
+        let generation = Arc::new(AtomicUsize::new(0));
+        let worker_generation = generation.clone();
+
+        let handle = thread::spawn(move || {
+            for (query, kind, expected) in query_rx {
+                if worker_generation.load(Ordering::Relaxed) != expected {
+                    continue;
+                }
+
+                let ranked = dict.find_terms_ranked(&query, kind, &worker_generation, expected);
+
+                if worker_generation.load(Ordering::Relaxed) == expected {
+                    let _ = results_tx.send((expected, ranked));
+                }
+            }
+        });
+
+        Self {
+            query_tx: Some(query_tx),
+            generation,
+            handle: Some(handle)
+        }
+    }
+
+    /// Submit a new query, superseding whatever the worker is currently searching for. Returns
+    /// the generation number assigned to this query, matching what arrives on the results channel.
+    pub fn search(&self, query: String, kind: SearchKind) -> usize {
+        let expected = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(tx) = &self.query_tx {
+            let _ = tx.send((query, kind, expected));
+        }
+        expected
+    }
+}
+
+impl Drop for SearchWorker {
+    fn drop(&mut self) {
+        // Close the channel first so the worker thread's loop can exit, then join it
+        self.query_tx.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchWorker, SyncWordDict};
+    use crate::{FuzzPriority, SearchKind};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{mpsc, Arc};
+
+    #[test]
+    fn it_saves_and_finds_strings() {
+        let mut dict = SyncWordDict::new();
+        dict.learn(String::from("hello"));
+
+        let generation = AtomicUsize::new(0);
+        assert!( dict.find_terms("hello", SearchKind::Strict, &generation, 0).len() == 1 );
+        assert!( dict.find_terms("hell", SearchKind::Strict, &generation, 0).len() == 0 );
+        assert!( dict.find_terms("hell", SearchKind::Prefix(10), &generation, 0).len() == 1 );
+    }
+
+    #[test]
+    fn it_searches_by_edit_distance() {
+        let mut dict = SyncWordDict::new();
+        dict.learn(String::from("hello"));
+        dict.learn(String::from("world"));
+
+        let generation = AtomicUsize::new(0);
+        assert!( dict.find_terms("hallo", SearchKind::EditDistance(1), &generation, 0).len() == 1 );
+        assert!( dict.find_terms("hallo", SearchKind::EditDistance(0), &generation, 0).len() == 0 );
+    }
+
+    #[test]
+    fn it_abandons_a_superseded_walk() {
+        let mut dict = SyncWordDict::new();
+        dict.learn(String::from("Hello World"));
+
+        // The generation the walk is started with (2) no longer matches what's now current (1),
+        // so it must give up immediately instead of returning a match
+        let generation = AtomicUsize::new(1);
+        assert!( dict.find_terms("hello", SearchKind::Strict, &generation, 2).len() == 0 );
+    }
+
+    #[test]
+    fn it_streams_ranked_results_from_the_worker() {
+        let mut dict = SyncWordDict::new();
+        dict.learn(String::from("Hello World"));
+        dict.learn(String::from("World Is Mine"));
+
+        let (tx, rx) = mpsc::channel();
+        let worker = SearchWorker::spawn(Arc::new(dict), tx);
+
+        let expected_generation = worker.search(String::from("helwor"), SearchKind::Fuzzy(5, FuzzPriority::TypoCorrection));
+        let (generation, ranked) = rx.recv().expect("worker should send back a result");
+
+        assert_eq!( generation, expected_generation );
+        assert!( ranked.iter().any(|(term, _)| term.as_str() == "Hello World") );
+    }
+}